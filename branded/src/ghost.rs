@@ -0,0 +1,163 @@
+//! A first-class [GhostCell](https://plv.mpi-sws.org/rustbelt/ghostcell/paper.pdf)
+//! implementation built on top of [`Brand`].
+//!
+//! A [`GhostToken`] is the *permission* to access data; a [`GhostCell`] is the *data*
+//! itself. Many `GhostCell`s can share a single brand, and all of them are governed by
+//! the one [`GhostToken`] carrying that same brand: holding `&token` lets you
+//! [`borrow`](GhostCell::borrow) any same-branded cell, and holding `&mut token` lets
+//! you [`borrow_mut`](GhostCell::borrow_mut) one, with no per-cell locking at runtime.
+//!
+//! Soundness comes entirely from the brand: because the invariant `'brand` lifetime
+//! uniquely ties every cell to exactly one token, holding `&mut GhostToken` proves that
+//! no other borrow of *any* same-branded cell is live, so the `&mut T` handed out can't
+//! alias. That's what lets graphs and doubly-linked lists be built out of freely-aliased
+//! `&GhostCell` nodes, yet still be mutated safely.
+
+use core::cell::UnsafeCell;
+
+use crate::{Brand, BrandGuard};
+
+/// The permission to access any [`GhostCell`] sharing the same `'brand`.
+///
+/// A shared `&GhostToken` grants shared access to those cells; an exclusive
+/// `&mut GhostToken` grants exclusive access, mirroring [`RwLock`](core) read/write
+/// semantics but checked entirely at compile time.
+pub struct GhostToken<'brand> {
+	brand: Brand<'brand>,
+}
+
+impl<'brand> GhostToken<'brand> {
+	/// Consumes a [`BrandGuard`] from [`make_brand!`](crate::make_brand), minting the
+	/// one token that will ever govern its brand.
+	///
+	/// This deliberately takes a `BrandGuard` rather than a bare [`Brand`]: `Brand`
+	/// is `Copy`, so a constructor that accepted one directly could be called twice
+	/// to mint two tokens governing the same brand, and those two tokens could then
+	/// hand out two aliasing `&mut` to one cell. `BrandGuard` isn't `Copy` — it's
+	/// only ever produced, uniquely, by `make_brand!` — so it can be consumed here at
+	/// most once per brand.
+	///
+	/// ```
+	/// use branded::make_brand;
+	/// use branded::ghost::{GhostCell, GhostToken};
+	///
+	/// make_brand!(b);
+	/// let cell = GhostCell::new(0, *b);
+	/// let mut token = GhostToken::new(b);
+	/// *cell.borrow_mut(&mut token) += 1;
+	/// ```
+	///
+	/// `BrandGuard` isn't `Copy`, so it can't be consumed twice to mint two tokens
+	/// for the same brand:
+	///
+	/// ```rust,compile_fail,E0382
+	/// use branded::make_brand;
+	/// use branded::ghost::GhostToken;
+	///
+	/// make_brand!(b);
+	/// let token1 = GhostToken::new(b);
+	/// let token2 = GhostToken::new(b); // ERROR: use of moved value `b`
+	/// ```
+	pub fn new(guard: BrandGuard<'brand>) -> Self {
+		Self { brand: *guard }
+	}
+}
+
+/// A data cell whose access is governed by the [`GhostToken`] sharing its `'brand`.
+///
+/// `GhostCell` carries no lock of its own; all the bookkeeping is the brand. The
+/// `unsafe` access to the value is in [`borrow`](Self::borrow) and
+/// [`borrow_mut`](Self::borrow_mut), where the token's borrow proves exclusivity;
+/// [`from_mut`](Self::from_mut) also uses `unsafe`, for a layout-compatible pointer
+/// cast.
+#[repr(transparent)]
+pub struct GhostCell<'brand, T: ?Sized> {
+	brand: Brand<'brand>,
+	value: UnsafeCell<T>,
+}
+
+impl<'brand, T> GhostCell<'brand, T> {
+	/// Creates a new cell branded with `brand`.
+	pub fn new(value: T, brand: Brand<'brand>) -> Self {
+		Self {
+			brand,
+			value: UnsafeCell::new(value),
+		}
+	}
+
+	/// Consumes the cell, returning the wrapped value. No token needed: owning the
+	/// cell already proves there are no other borrows of it.
+	pub fn into_inner(self) -> T {
+		self.value.into_inner()
+	}
+}
+
+impl<'brand, T: ?Sized> GhostCell<'brand, T> {
+	/// Reinterprets an existing `&mut T` as a `&mut GhostCell<'brand, T>`.
+	///
+	/// No token is needed: an exclusive reference to the value already proves there
+	/// are no other borrows of it. `brand` only exists to pin `'brand` to the same
+	/// lifetime as `value`'s borrow, so it's unused.
+	pub fn from_mut(value: &'brand mut T, _brand: Brand<'brand>) -> &'brand mut Self {
+		// SAFETY: `GhostCell` is `repr(transparent)` over `UnsafeCell<T>`, which is
+		// itself `repr(transparent)` over `T`, so the layouts match exactly.
+		unsafe { &mut *(value as *mut T as *mut Self) }
+	}
+
+	/// Grants shared access to the value, proven by the shared borrow of `token`.
+	pub fn borrow<'a>(&'a self, token: &'a GhostToken<'brand>) -> &'a T {
+		let _ = &token.brand;
+		// SAFETY: `token` and `self` share `'brand`, and `&GhostToken` proves no
+		// `&mut` borrow of any same-branded cell is live.
+		unsafe { &*self.value.get() }
+	}
+
+	/// Grants exclusive access to the value, proven by the exclusive borrow of
+	/// `token`.
+	pub fn borrow_mut<'a>(&'a self, token: &'a mut GhostToken<'brand>) -> &'a mut T {
+		let _ = &token.brand;
+		// SAFETY: `token` and `self` share `'brand`, and `&mut GhostToken` proves no
+		// other borrow of any same-branded cell is live.
+		unsafe { &mut *self.value.get() }
+	}
+
+	/// Grants exclusive access to the value with no token needed, since `&mut self`
+	/// already proves there are no other borrows of this cell.
+	pub fn get_mut(&mut self) -> &mut T {
+		self.value.get_mut()
+	}
+}
+
+// SAFETY: a `GhostCell` can be sent to another thread exactly when its contents could
+// be, same as `UnsafeCell`/`RwLock`.
+unsafe impl<'brand, T: ?Sized + Send> Send for GhostCell<'brand, T> {}
+// SAFETY: concurrent `borrow`s from multiple threads hand out concurrent `&T`s, so
+// sharing a `GhostCell` across threads requires `T: Sync`, same as `RwLock`.
+unsafe impl<'brand, T: ?Sized + Send + Sync> Sync for GhostCell<'brand, T> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::make_brand;
+
+	#[test]
+	fn borrow_and_borrow_mut_see_each_others_writes() {
+		make_brand!(b);
+		let cell = GhostCell::new(0, *b);
+		let mut token = GhostToken::new(b);
+
+		*cell.borrow_mut(&mut token) += 1;
+		assert_eq!(*cell.borrow(&token), 1);
+	}
+
+	#[test]
+	fn from_mut_reinterprets_in_place() {
+		let mut value = 41;
+		make_brand!(b);
+		let cell = GhostCell::from_mut(&mut value, *b);
+		let mut token = GhostToken::new(b);
+
+		*cell.borrow_mut(&mut token) += 1;
+		assert_eq!(*cell.borrow(&token), 42);
+	}
+}
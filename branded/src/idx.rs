@@ -0,0 +1,433 @@
+//! Branded indices and ranges into a [`FastVec`], validated once and then trusted.
+//!
+//! [`FastVec::make_idx`] checks a single index against the vector's length; the
+//! returned [`Idx`] is then trusted forever, so [`FastVec::get`] can skip bounds
+//! checks entirely. [`FastVec::range`] does the same for a whole span at once,
+//! returning a [`Range`] that can be refined, split, and iterated without re-checking
+//! each element — because the brand tying these tokens to their `FastVec` is
+//! invariant, there's no way to accidentally use one against a different vector.
+//!
+//! [`FastVec::push`] and [`FastVec::extend`] grow the vector while preserving its
+//! brand: because growth only ever appends, every index issued before the call stays
+//! in-bounds, so there's nothing to revalidate. There is deliberately no analogous
+//! `pop`/`truncate` that keeps the same brand — shrinking could invalidate indices
+//! that are already trusted to be in-bounds, so doing that soundly requires starting
+//! a fresh brand scope instead, the same way a handle from one handle-manager must
+//! never be used against another.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::Brand;
+
+/// A branded, pre-validated index into a [`FastVec<'b, T>`].
+#[derive(Clone, Copy)]
+pub struct Idx<'b> {
+	idx: usize,
+	_b: Brand<'b>,
+}
+
+impl<'b> Idx<'b> {
+	/// The raw, zero-based index this token was validated against.
+	pub fn get(self) -> usize {
+		self.idx
+	}
+}
+
+/// A `Vec` branded with a unique lifetime, so that [`Idx`]/[`Range`] tokens validated
+/// against it can never be confused with those of another `FastVec`.
+///
+/// Mismatched brands are rejected at compile time, not just between unrelated vectors
+/// but between two scopes of the very same call:
+///
+/// ```rust,compile_fail,E0308
+/// use branded::idx::FastVec;
+///
+/// FastVec::new(vec![0, 1, 2], |fv1| {
+///     FastVec::new(vec![3, 4, 5], |fv2| {
+///         let idx = fv1.make_idx(0).unwrap();
+///         fv2.get(idx); // ERROR: `idx` is branded for `fv1`, not `fv2`
+///     });
+/// });
+/// ```
+pub struct FastVec<'b, T> {
+	inner: Vec<T>,
+	b: Brand<'b>,
+}
+
+impl<'b, T> FastVec<'b, T> {
+	/// Creates a new branded `FastVec`, passing it to a closure for use.
+	pub fn new(inner: Vec<T>, f: impl for<'new_brand> FnOnce(FastVec<'new_brand, T>)) {
+		Brand::new(|b: Brand<'_>| {
+			f(FastVec { inner, b });
+		});
+	}
+
+	/// One-time validation that `idx` is in bounds.
+	pub fn make_idx(&self, idx: usize) -> Option<Idx<'b>> {
+		if idx < self.inner.len() {
+			Some(Idx { idx, _b: self.b })
+		} else {
+			None
+		}
+	}
+
+	/// The whole valid range of this vector, with no emptiness proof yet.
+	pub fn range(&self) -> Range<'b, Unknown> {
+		Range {
+			start: 0,
+			end: self.inner.len(),
+			b: self.b,
+			_proof: PhantomData,
+		}
+	}
+
+	/// Skips bounds checking, using an already-validated index.
+	pub fn get(&self, idx: Idx<'b>) -> &T {
+		unsafe { self.inner.get_unchecked(idx.idx) }
+	}
+
+	/// Skips bounds checking, using an already-validated index.
+	pub fn get_mut(&mut self, idx: Idx<'b>) -> &mut T {
+		unsafe { self.inner.get_unchecked_mut(idx.idx) }
+	}
+
+	/// The number of elements currently in the vector.
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	/// Whether the vector currently holds no elements.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	/// Appends `value`, returning a trusted index to the freshly inserted element.
+	///
+	/// Growth only ever appends, so every index issued before this call stays
+	/// in-bounds: the brand is preserved with no need to revalidate anything.
+	pub fn push(&mut self, value: T) -> Idx<'b> {
+		self.inner.push(value);
+		Idx {
+			idx: self.inner.len() - 1,
+			_b: self.b,
+		}
+	}
+
+	/// Appends every item of `iter`, returning a trusted [`Range`] covering the
+	/// appended tail. Preserves the brand for the same reason as
+	/// [`push`](Self::push).
+	pub fn extend(&mut self, iter: impl IntoIterator<Item = T>) -> Range<'b, Unknown> {
+		let start = self.inner.len();
+		self.inner.extend(iter);
+		Range {
+			start,
+			end: self.inner.len(),
+			b: self.b,
+			_proof: PhantomData,
+		}
+	}
+
+	/// Splits the vector at `mid` into two independently-branded mutable halves,
+	/// calling `f` with both, the branded analogue of `slice::split_at_mut`.
+	///
+	/// Each half gets its own fresh, invariant brand, so an `Idx<'l>` can never be
+	/// passed to the right half's `get` (or vice versa): mixing them up is a compile
+	/// error, while both halves remain mutable at the same time.
+	pub fn split_at_mut<R>(
+		&mut self,
+		mid: Idx<'b>,
+		f: impl for<'l, 'r> FnOnce(FastVecMut<'_, 'l, T>, FastVecMut<'_, 'r, T>) -> R,
+	) -> R {
+		let (left, right) = self.inner.split_at_mut(mid.idx);
+		Brand::new(move |lb: Brand<'_>| {
+			Brand::new(move |rb: Brand<'_>| {
+				f(FastVecMut { inner: left, b: lb }, FastVecMut { inner: right, b: rb })
+			})
+		})
+	}
+}
+
+/// A borrowed, branded view over a `&'data mut [T]`, handed out by
+/// [`FastVec::split_at_mut`] so that each half of a split vector carries its own
+/// brand.
+///
+/// `'data` (the slice borrow) and `'brand` (the invariant brand tag) are kept as two
+/// separate parameters on purpose: `split_at_mut` borrows both halves from the same
+/// `&mut self` call, so they share one `'data`, while each still needs its *own*,
+/// independently-fresh `'brand`. Conflating the two into a single parameter would
+/// force the slice borrow itself to be no longer than the fresh brand scope, which
+/// the `for<'b>` closures passed to `Brand::new` can't satisfy.
+pub struct FastVecMut<'data, 'brand, T> {
+	inner: &'data mut [T],
+	b: Brand<'brand>,
+}
+
+impl<'data, 'brand, T> FastVecMut<'data, 'brand, T> {
+	/// One-time validation that `idx` is in bounds.
+	pub fn make_idx(&self, idx: usize) -> Option<Idx<'brand>> {
+		if idx < self.inner.len() {
+			Some(Idx { idx, _b: self.b })
+		} else {
+			None
+		}
+	}
+
+	/// The whole valid range of this half, with no emptiness proof yet.
+	pub fn range(&self) -> Range<'brand, Unknown> {
+		Range {
+			start: 0,
+			end: self.inner.len(),
+			b: self.b,
+			_proof: PhantomData,
+		}
+	}
+
+	/// Skips bounds checking, using an already-validated index.
+	pub fn get(&self, idx: Idx<'brand>) -> &T {
+		unsafe { self.inner.get_unchecked(idx.idx) }
+	}
+
+	/// Skips bounds checking, using an already-validated index.
+	pub fn get_mut(&mut self, idx: Idx<'brand>) -> &mut T {
+		unsafe { self.inner.get_unchecked_mut(idx.idx) }
+	}
+
+	/// The number of elements in this half.
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	/// Whether this half holds no elements.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+}
+
+mod sealed {
+	pub trait Sealed {}
+}
+
+/// Marks a proof carried by a [`Range`]. Sealed: the only proofs are [`Unknown`] and
+/// [`NonEmpty`].
+pub trait RangeProof: sealed::Sealed {}
+
+/// Proof that a [`Range`] has not (yet) been checked for emptiness.
+pub enum Unknown {}
+/// Proof, checked once via [`Range::nonempty`], that a [`Range`] has at least one
+/// element.
+pub enum NonEmpty {}
+
+impl sealed::Sealed for Unknown {}
+impl sealed::Sealed for NonEmpty {}
+impl RangeProof for Unknown {}
+impl RangeProof for NonEmpty {}
+
+/// A branded, pre-validated span of indices into a [`FastVec<'b, T>`], carrying a
+/// zero-sized proof `P` about whether it's known to be non-empty.
+pub struct Range<'b, P: RangeProof> {
+	start: usize,
+	end: usize,
+	b: Brand<'b>,
+	_proof: PhantomData<P>,
+}
+
+impl<'b, P: RangeProof> Range<'b, P> {
+	/// The number of indices covered by this range.
+	pub fn len(&self) -> usize {
+		self.end - self.start
+	}
+
+	/// Whether this range currently covers zero indices.
+	pub fn is_empty(&self) -> bool {
+		self.start == self.end
+	}
+
+	/// Splits the range at `i`, into the indices before `i` and `i` onward. Either
+	/// half may turn out to be empty, so both come back as [`Unknown`].
+	///
+	/// # Panics
+	/// Panics if `i` doesn't fall within `self` (i.e. isn't in `self.start..=self.end`).
+	/// `i` being a trusted [`Idx`] only proves it's in bounds for the whole `FastVec`,
+	/// not for this particular sub-range.
+	pub fn split_at(self, i: Idx<'b>) -> (Range<'b, Unknown>, Range<'b, Unknown>) {
+		assert!(
+			self.start <= i.idx && i.idx <= self.end,
+			"split index {} out of range for Range {{ start: {}, end: {} }}",
+			i.idx,
+			self.start,
+			self.end
+		);
+		(
+			Range {
+				start: self.start,
+				end: i.idx,
+				b: self.b,
+				_proof: PhantomData,
+			},
+			Range {
+				start: i.idx,
+				end: self.end,
+				b: self.b,
+				_proof: PhantomData,
+			},
+		)
+	}
+}
+
+impl<'b> Range<'b, Unknown> {
+	/// Checks, once, whether this range contains any elements, refining it into a
+	/// [`NonEmpty`] range on success.
+	pub fn nonempty(self) -> Result<Range<'b, NonEmpty>, Range<'b, Unknown>> {
+		if self.start < self.end {
+			Ok(Range {
+				start: self.start,
+				end: self.end,
+				b: self.b,
+				_proof: PhantomData,
+			})
+		} else {
+			Err(self)
+		}
+	}
+}
+
+impl<'b> Range<'b, NonEmpty> {
+	/// The first index in the range. Trusted with no runtime check, since the
+	/// `NonEmpty` proof already guarantees there's at least one element.
+	pub fn first(&self) -> Idx<'b> {
+		Idx {
+			idx: self.start,
+			_b: self.b,
+		}
+	}
+
+	/// The last index in the range. Trusted with no runtime check, for the same
+	/// reason as [`first`](Self::first).
+	pub fn last(&self) -> Idx<'b> {
+		Idx {
+			idx: self.end - 1,
+			_b: self.b,
+		}
+	}
+}
+
+impl<'b, P: RangeProof> IntoIterator for Range<'b, P> {
+	type Item = Idx<'b>;
+	type IntoIter = Iter<'b>;
+
+	fn into_iter(self) -> Iter<'b> {
+		Iter {
+			start: self.start,
+			end: self.end,
+			b: self.b,
+		}
+	}
+}
+
+/// A [`DoubleEndedIterator`] over the branded [`Idx`]es of a [`Range`].
+pub struct Iter<'b> {
+	start: usize,
+	end: usize,
+	b: Brand<'b>,
+}
+
+impl<'b> Iterator for Iter<'b> {
+	type Item = Idx<'b>;
+
+	fn next(&mut self) -> Option<Idx<'b>> {
+		if self.start < self.end {
+			let idx = Idx {
+				idx: self.start,
+				_b: self.b,
+			};
+			self.start += 1;
+			Some(idx)
+		} else {
+			None
+		}
+	}
+}
+
+impl<'b> DoubleEndedIterator for Iter<'b> {
+	fn next_back(&mut self) -> Option<Idx<'b>> {
+		if self.start < self.end {
+			self.end -= 1;
+			Some(Idx {
+				idx: self.end,
+				_b: self.b,
+			})
+		} else {
+			None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec;
+
+	use super::*;
+
+	#[test]
+	fn push_and_extend_preserve_brand() {
+		FastVec::new(vec![0, 1, 2], |mut fv| {
+			let first = fv.push(3);
+			assert_eq!(*fv.get(first), 3);
+
+			let tail = fv.extend([4, 5]);
+			assert_eq!(tail.len(), 2);
+			assert_eq!(fv.len(), 6);
+		});
+	}
+
+	#[test]
+	fn range_nonempty_and_iter() {
+		FastVec::new(vec![0, 1, 2], |fv| {
+			let whole = match fv.range().nonempty() {
+				Ok(whole) => whole,
+				Err(_) => panic!("range should be non-empty"),
+			};
+			assert_eq!(*fv.get(whole.first()), 0);
+			assert_eq!(*fv.get(whole.last()), 2);
+
+			let collected: Vec<_> = whole.into_iter().map(|idx| *fv.get(idx)).collect();
+			assert_eq!(collected, vec![0, 1, 2]);
+		});
+	}
+
+	#[test]
+	fn split_at_splits_on_the_given_index() {
+		FastVec::new(vec![0, 1, 2, 3], |fv| {
+			let mid = fv.make_idx(2).unwrap();
+			let (before, after) = fv.range().split_at(mid);
+			assert_eq!(before.len(), 2);
+			assert_eq!(after.len(), 2);
+		});
+	}
+
+	#[test]
+	#[should_panic(expected = "out of range")]
+	fn split_at_panics_outside_sub_range() {
+		FastVec::new(vec![0, 1, 2, 3], |fv| {
+			let (before, _) = fv.range().split_at(fv.make_idx(2).unwrap());
+			// `3` is a valid index for the whole vector, but not for `before`.
+			before.split_at(fv.make_idx(3).unwrap());
+		});
+	}
+
+	#[test]
+	fn split_at_mut_gives_two_independently_branded_halves() {
+		FastVec::new(vec![0, 1, 2, 3], |mut fv| {
+			let mid = fv.make_idx(2).unwrap();
+			fv.split_at_mut(mid, |mut left, mut right| {
+				let l0 = left.make_idx(0).unwrap();
+				let r0 = right.make_idx(0).unwrap();
+				*left.get_mut(l0) += 10;
+				*right.get_mut(r0) += 20;
+				assert_eq!(*left.get(l0), 10);
+				assert_eq!(*right.get(r0), 22);
+			});
+		});
+	}
+}
@@ -5,19 +5,22 @@
 //! free and can be done over and over, with each generated lifetime being unique.
 //!
 //! # Example Use Cases
-//! - Zero-cost array indexing. Check that your index is in bounds *once*, then brand
-//!   the index. Branded indices can safely skip bounds checks, because you already
-//!   verified it beforehand. You'll get a compile error if you ever try to use
-//!   mismatched brands.
-//! - The [GhostCell] datastructure, which lets you safely separate a `Mutex` or other
-//!   lock from the actual data it is protecting. This lets certain data structures,
-//!   like graphs or linked lists, be implemented much more safely without resorting to
-//!   wrapping each node in a Mutex
-//! -
+//! - Zero-cost array indexing, via [`idx::FastVec`]. Check that your index (or a
+//!   whole [`idx::Range`]) is in bounds *once*, then brand it. Branded indices can
+//!   safely skip bounds checks, because you already verified it beforehand. You'll
+//!   get a compile error if you ever try to use mismatched brands.
+//! - The [`ghost::GhostCell`] datastructure, which lets you safely separate a `Mutex`
+//!   or other lock from the actual data it is protecting. This lets certain data
+//!   structures, like graphs or linked lists, be implemented much more safely without
+//!   resorting to wrapping each node in a Mutex.
 //!
 //! # Safety of this library
-//! The only unsafe code is in the examples. There is no `unsafe` in any of the actual
-//! library code.
+//! Brand creation (`Brand::new`, `make_brand!`) is a safe API, built on the `unsafe`
+//! [`Brand::new_unchecked`]. The [`idx`] module uses `unsafe` unchecked indexing in
+//! `get`/`get_mut`, where a branded index is what makes the access sound. The
+//! [`ghost`] module uses `unsafe` in `borrow`/`borrow_mut` (sound because of the
+//! brand), in `from_mut` (a layout-compatible pointer cast), and in its `Send`/`Sync`
+//! impls.
 //!
 //! However, because `branded` allows users of the library to assume certain properties
 //! such as uniquess of a generated `Brand`s lifetime, a bug in branded where that
@@ -32,10 +35,25 @@
 
 #![no_std]
 
+extern crate alloc;
+
 use core::marker::PhantomData;
 
+pub mod ghost;
+pub mod idx;
+
 /// Represents a branded lifetime. Branded lifetimes are guaranteed to be unique.
-#[derive(Default, Copy, Clone)]
+///
+/// Deliberately not `Default`: `Brand::default()` would let anyone forge a brand for
+/// any lifetime with no [`Brand::new`]/[`make_brand!`] involved at all, defeating the
+/// uniqueness this whole crate relies on.
+///
+/// ```rust,compile_fail,E0599
+/// use branded::Brand;
+///
+/// let _: Brand<'static> = Brand::default(); // ERROR: no function `default`
+/// ```
+#[derive(Copy, Clone)]
 pub struct Brand<'brand> {
 	/// Phantom lifetime type that is invariant in `'a`. Because it is invariant, only the
 	/// exact, original lifetime will match.
@@ -48,11 +66,23 @@ impl<'brand> Brand<'brand> {
 	/// Creates a new `Brand`, which will be passed to a closure `f`. Then calls `f`
 	/// and returns its returned value of type `R`.
 	pub fn new<R, F: WithBrand<R>>(f: F) -> R {
-		let b = Self {
-			_marker: PhantomData,
-		};
+		let b = unsafe { Self::new_unchecked() };
 		f(b)
 	}
+
+	/// Creates a new `Brand` without tying it to a closure.
+	///
+	/// # Safety
+	/// The caller must ensure the returned `Brand`'s lifetime is never unified with
+	/// that of any other `Brand`, e.g. by only ever naming it through a fresh,
+	/// anonymous lifetime. [`Brand::new`] and [`make_brand!`] are the only callers
+	/// that are guaranteed to uphold this, so prefer those over calling this
+	/// directly.
+	pub unsafe fn new_unchecked() -> Self {
+		Self {
+			_marker: PhantomData,
+		}
+	}
 }
 
 /// Any `FnOnce` closure that accepts a `Brand` and returns an `R`.
@@ -64,6 +94,124 @@ pub trait WithBrand<R = ()>: for<'b> FnOnce(Brand<'b>) -> R {}
 /// Implements `WithBrand` on every posible closure.
 impl<F, R> WithBrand<R> for F where F: for<'b> FnOnce(Brand<'b>) -> R {}
 
+/// A scope guard, created by [`make_brand!`], that owns a uniquely-branded [`Brand`].
+///
+/// `BrandGuard` derefs to the `Brand` it holds, so most code never needs to name this
+/// type directly; it only needs to exist long enough to keep its brand alive.
+pub struct BrandGuard<'brand> {
+	brand: Brand<'brand>,
+}
+
+impl<'brand> core::ops::Deref for BrandGuard<'brand> {
+	type Target = Brand<'brand>;
+	fn deref(&self) -> &Brand<'brand> {
+		&self.brand
+	}
+}
+
+impl<'brand> BrandGuard<'brand> {
+	/// Used by [`make_brand!`]; not meant to be called directly.
+	///
+	/// # Safety
+	/// See [`Brand::new_unchecked`]. `brand` must be one that [`BrandTether`] has
+	/// already entangled with a real, uniquely-scoped borrow, which is exactly what
+	/// `make_brand!` does and nothing else can.
+	#[doc(hidden)]
+	pub unsafe fn new_unchecked(brand: Brand<'brand>) -> Self {
+		Self { brand }
+	}
+}
+
+/// Entangles a [`Brand`] with the drop timing of a real, local borrow.
+///
+/// This is what actually makes [`make_brand!`]'s brands generative (unique). A bare
+/// `Drop` impl on a type holding an unconstrained `Brand<'brand>` is *not* enough on
+/// its own: nothing stops the compiler from picking the very same `'brand` for two
+/// separate guards, since each guard's lifetime parameter would otherwise be a free
+/// variable with no real borrow tying it down. `BrandTether::new` takes `&'brand
+/// Brand<'brand>` — an honest borrow of a specific local, for exactly `'brand` — and
+/// its `Drop` impl forces the compiler to treat that borrow, and therefore `'brand`,
+/// as live until this exact, per-invocation drop point. Two different `make_brand!`
+/// expansions create two different tethers with two different (and differently
+/// ordered) drop points, so their `'brand`s can never be unified.
+#[doc(hidden)]
+pub struct BrandTether<'brand> {
+	_marker: PhantomData<&'brand Brand<'brand>>,
+}
+
+impl<'brand> Drop for BrandTether<'brand> {
+	// Left blank on purpose: the only reason this type has a `Drop` impl at all is to
+	// give it drop glue, which is what forces the borrow it holds (and so `'brand`)
+	// to be considered live up to this precise point in the generated code.
+	#[inline(always)]
+	fn drop(&mut self) {}
+}
+
+impl<'brand> BrandTether<'brand> {
+	/// Used by [`make_brand!`]; not meant to be called directly.
+	///
+	/// # Safety
+	/// The `&'brand Brand<'brand>` passed in must be a genuine borrow of a brand that
+	/// was *just* created, so that its borrow's drop timing is unique to this
+	/// invocation; nothing pre-existing may be passed here.
+	#[doc(hidden)]
+	#[inline(always)]
+	pub unsafe fn new(_: &'brand Brand<'brand>) -> Self {
+		Self {
+			_marker: PhantomData,
+		}
+	}
+}
+
+/// Brands a local variable with a unique lifetime, without needing a closure.
+///
+/// `Brand::new` forces all branded code into a `for<'b> FnOnce(Brand<'b>)` closure,
+/// which nests badly as soon as a function wants several independent brands, or wants
+/// to return a branded value up the stack. `make_brand!` avoids this by binding the
+/// brand to an ordinary local variable instead:
+///
+/// ```
+/// use branded::make_brand;
+///
+/// make_brand!(b);
+/// // `b` derefs to a `Brand<'_>`, usable anywhere a `Brand` is expected.
+/// ```
+///
+/// Two invocations (even back to back) can never have their brands unified by the
+/// borrow checker, because each one's [`BrandTether`] ties its `'brand` to a distinct,
+/// per-invocation drop point:
+///
+/// ```rust,compile_fail,E0597
+/// use branded::{make_brand, Brand};
+///
+/// fn same<'x>(_: Brand<'x>, _: Brand<'x>) {}
+///
+/// make_brand!(a);
+/// make_brand!(b);
+/// same(*a, *b); // ERROR: `'brand` of `a` can never unify with that of `b`
+/// ```
+#[macro_export]
+macro_rules! make_brand {
+	($name:ident) => {
+		let brand = unsafe { $crate::Brand::new_unchecked() };
+		#[allow(unused)]
+		let brand_tether = unsafe { $crate::BrandTether::new(&brand) };
+		let $name = unsafe { $crate::BrandGuard::new_unchecked(brand) };
+
+		// Ensures a `BrandTether` is dropped on every path, even through code that
+		// diverges after this macro; see the `generativity` crate's `make_guard!`
+		// for the history of why this matters.
+		if let $crate::__private::Some(x) = $crate::__private::None {
+			return x;
+		}
+	};
+}
+
+#[doc(hidden)]
+pub mod __private {
+	pub use core::option::Option::{None, Some};
+}
+
 #[cfg(test)]
 mod tests {
 